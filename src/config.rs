@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+
+use serde::Deserialize;
+
+/// User overrides loaded from `rsdbgen.toml` in the current directory, so
+/// a project can adapt generation to its own conventions without forking
+/// rsdbgen.
+///
+/// Every field defaults to empty, so a project with no `rsdbgen.toml` (or
+/// one that only sets a couple of fields) gets the built-in behaviour.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Maps a backend's column type name (e.g. Postgres `jsonb`) to a
+    /// Rust type to use instead of the built-in mapping, such as a
+    /// project's own `serde_json`-backed struct.
+    pub type_overrides: HashMap<String, String>,
+    /// Derives applied to every generated struct in addition to the
+    /// baseline ones (`Debug`, `Clone`, and `sqlx::FromRow` on row
+    /// structs), e.g. `serde::Serialize`.
+    pub derives: Vec<String>,
+    /// Table names are emitted if they match any `include` glob (or if
+    /// `include` is empty, by default) and none of the `exclude` globs.
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// Maps a column name to the Rust field name to emit for it.
+    pub field_renames: HashMap<String, String>,
+}
+
+impl Config {
+    const DEFAULT_PATH: &'static str = "rsdbgen.toml";
+
+    /// Loads `rsdbgen.toml` from the current directory, or falls back to
+    /// `Config::default()` if it isn't there.
+    pub fn load() -> Result<Self, anyhow::Error> {
+        match fs::read_to_string(Self::DEFAULT_PATH) {
+            Ok(contents) => Ok(toml::from_str(&contents)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Whether `table_name` should be emitted, replacing the old
+    /// hard-coded `should_emit` exclusion with `include`/`exclude` globs.
+    pub fn should_emit(&self, table_name: &str) -> bool {
+        if table_name == "_sqlx_migrations" {
+            return false;
+        }
+        let included = self.include.is_empty() || self.include.iter().any(|pat| glob_match(pat, table_name));
+        let excluded = self.exclude.iter().any(|pat| glob_match(pat, table_name));
+        included && !excluded
+    }
+
+    /// The Rust field name to emit for `column_name`, applying
+    /// `field_renames` if it has an entry for it.
+    pub fn field_name(&self, column_name: &str) -> String {
+        self.field_renames
+            .get(column_name)
+            .cloned()
+            .unwrap_or_else(|| column_name.to_string())
+    }
+}
+
+fn glob_match(pattern: &str, value: &str) -> bool {
+    glob::Pattern::new(pattern)
+        .map(|compiled| compiled.matches(value))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_match_supports_wildcards() {
+        assert!(glob_match("user*", "users"));
+        assert!(!glob_match("user*", "orders"));
+    }
+
+    #[test]
+    fn should_emit_defaults_to_true_with_no_globs() {
+        let config = Config::default();
+        assert!(config.should_emit("users"));
+    }
+
+    #[test]
+    fn should_emit_always_excludes_sqlx_migrations() {
+        let config = Config::default();
+        assert!(!config.should_emit("_sqlx_migrations"));
+    }
+
+    #[test]
+    fn should_emit_applies_include_and_exclude_globs() {
+        let config = Config {
+            include: vec!["user*".to_string()],
+            exclude: vec!["user_secrets".to_string()],
+            ..Config::default()
+        };
+        assert!(config.should_emit("users"));
+        assert!(!config.should_emit("user_secrets"));
+        assert!(!config.should_emit("orders"));
+    }
+}
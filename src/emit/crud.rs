@@ -0,0 +1,338 @@
+use codegen::Scope;
+use itertools::Itertools;
+
+use super::{input_row_struct_name, row_struct_name};
+use crate::schema::{Backend, TypeResolver};
+
+/// Generates a `<Table>Repository` trait plus one async impl for
+/// `resolver`'s backend (`Pg.../MySql.../Sqlite{Table}Repository`, by
+/// backend) and a `#[cfg(feature = "blocking")]` blocking impl wrapping
+/// it, covering insert, select-by-id, select-all, update and delete for
+/// `table_name`.
+///
+/// The async impl runs the real queries with runtime `sqlx::query_as`
+/// (rather than the compile-time-checked `query_as!`), binding each
+/// column value so the generated code compiles without a live
+/// `DATABASE_URL`. The trait and impls mix attributes and generics that
+/// the `codegen` crate's struct/fn builders don't model, so they're
+/// emitted as a single raw block, the same way the hand-written query
+/// bodies already are.
+///
+/// Postgres and SQLite both support `RETURNING`, so their impls insert
+/// and update in one round trip. MySQL doesn't, so its impl inserts/updates
+/// without `RETURNING` and then re-selects the row by id (using
+/// `LAST_INSERT_ID()` for the freshly inserted row). `columns` is
+/// `(db_column_name, rust_field_name, udt_name, is_nullable, comment)`.
+///
+/// Skips the table (logging why) instead of emitting anything if it
+/// doesn't have a single `id` column, since the generated trait always
+/// keys select/update/delete on `id`; composite and custom-named primary
+/// keys aren't supported yet. Likewise skips tables with no columns
+/// besides `id`, since `insert`/`update` would have nothing to set.
+pub fn add_repository_for_table(
+    scope: &mut Scope,
+    resolver: &TypeResolver,
+    table_name: &str,
+    columns: &[(String, String, String, bool, Option<String>)],
+) {
+    let Some(id_column) = columns.iter().find(|c| c.0 == "id") else {
+        println!(
+            "Skipping CRUD repository for {table_name}: no `id` column found (composite and custom primary keys aren't supported yet)"
+        );
+        return;
+    };
+
+    let row_struct = row_struct_name(table_name);
+    let input_struct = input_row_struct_name(table_name);
+    let trait_name = format!("{}Repository", row_struct);
+    let id_type = resolver.resolve(&id_column.2);
+    let non_id_columns: Vec<_> = columns.iter().filter(|c| c.0 != "id").collect();
+    if non_id_columns.is_empty() {
+        println!(
+            "Skipping CRUD repository for {table_name}: no columns besides `id`, nothing to insert or update"
+        );
+        return;
+    }
+
+    let conn_type = resolver.connection_type();
+    let trait_block = repository_trait(&trait_name, &row_struct, &input_struct, &id_type, conn_type);
+    let async_impl = async_repository_impl(
+        resolver,
+        &trait_name,
+        table_name,
+        &row_struct,
+        &input_struct,
+        &id_type,
+        &non_id_columns,
+    );
+    let blocking_impl = blocking_repository_impl(
+        resolver.backend(),
+        &trait_name,
+        &row_struct,
+        &input_struct,
+        &id_type,
+        conn_type,
+    );
+
+    scope.raw(&format!("{trait_block}\n{async_impl}\n{blocking_impl}"));
+}
+
+/// The prefix used on the generated impl struct's name, e.g.
+/// `PgUserRepository` for a Postgres `users` table.
+fn impl_name_prefix(backend: Backend) -> &'static str {
+    match backend {
+        Backend::Postgres => "Pg",
+        Backend::MySql => "MySql",
+        Backend::Sqlite => "Sqlite",
+    }
+}
+
+fn repository_trait(trait_name: &str, row_struct: &str, input_struct: &str, id_type: &str, conn_type: &str) -> String {
+    format!(
+        r#"#[async_trait::async_trait]
+pub trait {trait_name} {{
+    async fn insert(conn: &mut {conn_type}, row: &{input_struct}) -> Result<{row_struct}, sqlx::Error>;
+    async fn select_by_id(conn: &mut {conn_type}, id: &{id_type}) -> Result<{row_struct}, sqlx::Error>;
+    async fn select_all(conn: &mut {conn_type}) -> Result<Vec<{row_struct}>, sqlx::Error>;
+    async fn update(conn: &mut {conn_type}, id: &{id_type}, row: &{input_struct}) -> Result<{row_struct}, sqlx::Error>;
+    async fn delete(conn: &mut {conn_type}, id: &{id_type}) -> Result<(), sqlx::Error>;
+}}"#
+    )
+}
+
+fn async_repository_impl(
+    resolver: &TypeResolver,
+    trait_name: &str,
+    table_name: &str,
+    row_struct: &str,
+    input_struct: &str,
+    id_type: &str,
+    non_id_columns: &[&(String, String, String, bool, Option<String>)],
+) -> String {
+    match resolver.backend() {
+        Backend::MySql => mysql_repository_impl(resolver, trait_name, table_name, row_struct, input_struct, id_type, non_id_columns),
+        Backend::Postgres | Backend::Sqlite => {
+            returning_repository_impl(resolver, trait_name, table_name, row_struct, input_struct, id_type, non_id_columns)
+        }
+    }
+}
+
+/// The insert/update bodies shared by every backend whose `sqlx` query
+/// builder accepts a `?`/`$n`-bound column list, i.e. everything but the
+/// `RETURNING` clause and the connection type.
+struct InsertUpdateParts {
+    column_list: String,
+    insert_placeholders: String,
+    insert_binds: String,
+    update_assignments: String,
+    update_binds: String,
+    update_id_placeholder: String,
+    id_placeholder: String,
+}
+
+fn insert_update_parts(
+    resolver: &TypeResolver,
+    non_id_columns: &[&(String, String, String, bool, Option<String>)],
+) -> InsertUpdateParts {
+    InsertUpdateParts {
+        column_list: non_id_columns.iter().map(|c| c.0.clone()).join(", "),
+        insert_placeholders: (1..=non_id_columns.len()).map(|i| resolver.placeholder(i)).join(", "),
+        insert_binds: non_id_columns.iter().map(|c| format!(".bind(&row.{})", c.1)).join("\n        "),
+        update_assignments: non_id_columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| format!("{} = {}", c.0, resolver.placeholder(i + 1)))
+            .join(", "),
+        update_binds: non_id_columns.iter().map(|c| format!(".bind(&row.{})", c.1)).join("\n        "),
+        update_id_placeholder: resolver.placeholder(non_id_columns.len() + 1),
+        id_placeholder: resolver.placeholder(1),
+    }
+}
+
+/// The Postgres and SQLite impl: both support `RETURNING`, so insert and
+/// update return the affected row in one round trip.
+fn returning_repository_impl(
+    resolver: &TypeResolver,
+    trait_name: &str,
+    table_name: &str,
+    row_struct: &str,
+    input_struct: &str,
+    id_type: &str,
+    non_id_columns: &[&(String, String, String, bool, Option<String>)],
+) -> String {
+    let conn_type = resolver.connection_type();
+    let impl_name = format!("{}{}Repository", impl_name_prefix(resolver.backend()), row_struct);
+    let p = insert_update_parts(resolver, non_id_columns);
+
+    format!(
+        r##"pub struct {impl_name};
+
+#[async_trait::async_trait]
+impl {trait_name} for {impl_name} {{
+    async fn insert(conn: &mut {conn_type}, row: &{input_struct}) -> Result<{row_struct}, sqlx::Error> {{
+        let result = sqlx::query_as::<_, {row_struct}>(
+            r#"INSERT INTO {table_name} ({column_list}) VALUES ({insert_placeholders}) RETURNING *"#,
+        )
+        {insert_binds}
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(result)
+    }}
+
+    async fn select_by_id(conn: &mut {conn_type}, id: &{id_type}) -> Result<{row_struct}, sqlx::Error> {{
+        let result = sqlx::query_as::<_, {row_struct}>(r#"SELECT * FROM {table_name} WHERE id = {id_placeholder}"#)
+            .bind(id)
+            .fetch_one(&mut *conn)
+            .await?;
+        Ok(result)
+    }}
+
+    async fn select_all(conn: &mut {conn_type}) -> Result<Vec<{row_struct}>, sqlx::Error> {{
+        let result = sqlx::query_as::<_, {row_struct}>(r#"SELECT * FROM {table_name}"#)
+            .fetch_all(&mut *conn)
+            .await?;
+        Ok(result)
+    }}
+
+    async fn update(conn: &mut {conn_type}, id: &{id_type}, row: &{input_struct}) -> Result<{row_struct}, sqlx::Error> {{
+        let result = sqlx::query_as::<_, {row_struct}>(
+            r#"UPDATE {table_name} SET {update_assignments} WHERE id = {update_id_placeholder} RETURNING *"#,
+        )
+        {update_binds}
+        .bind(id)
+        .fetch_one(&mut *conn)
+        .await?;
+        Ok(result)
+    }}
+
+    async fn delete(conn: &mut {conn_type}, id: &{id_type}) -> Result<(), sqlx::Error> {{
+        sqlx::query(r#"DELETE FROM {table_name} WHERE id = {id_placeholder}"#)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }}
+}}"##,
+        column_list = p.column_list,
+        insert_placeholders = p.insert_placeholders,
+        insert_binds = p.insert_binds,
+        update_assignments = p.update_assignments,
+        update_binds = p.update_binds,
+        update_id_placeholder = p.update_id_placeholder,
+        id_placeholder = p.id_placeholder,
+    )
+}
+
+/// The MySQL impl: no `RETURNING`, so insert/update run the bare
+/// statement and then re-select the row by id, using `LAST_INSERT_ID()`
+/// to learn the id of a freshly inserted row.
+fn mysql_repository_impl(
+    resolver: &TypeResolver,
+    trait_name: &str,
+    table_name: &str,
+    row_struct: &str,
+    input_struct: &str,
+    id_type: &str,
+    non_id_columns: &[&(String, String, String, bool, Option<String>)],
+) -> String {
+    let conn_type = resolver.connection_type();
+    let impl_name = format!("{}{}Repository", impl_name_prefix(resolver.backend()), row_struct);
+    let p = insert_update_parts(resolver, non_id_columns);
+
+    format!(
+        r##"pub struct {impl_name};
+
+#[async_trait::async_trait]
+impl {trait_name} for {impl_name} {{
+    async fn insert(conn: &mut {conn_type}, row: &{input_struct}) -> Result<{row_struct}, sqlx::Error> {{
+        let result = sqlx::query(
+            r#"INSERT INTO {table_name} ({column_list}) VALUES ({insert_placeholders})"#,
+        )
+        {insert_binds}
+        .execute(&mut *conn)
+        .await?;
+        let id = result.last_insert_id() as {id_type};
+        Self::select_by_id(conn, &id).await
+    }}
+
+    async fn select_by_id(conn: &mut {conn_type}, id: &{id_type}) -> Result<{row_struct}, sqlx::Error> {{
+        let result = sqlx::query_as::<_, {row_struct}>(r#"SELECT * FROM {table_name} WHERE id = {id_placeholder}"#)
+            .bind(id)
+            .fetch_one(&mut *conn)
+            .await?;
+        Ok(result)
+    }}
+
+    async fn select_all(conn: &mut {conn_type}) -> Result<Vec<{row_struct}>, sqlx::Error> {{
+        let result = sqlx::query_as::<_, {row_struct}>(r#"SELECT * FROM {table_name}"#)
+            .fetch_all(&mut *conn)
+            .await?;
+        Ok(result)
+    }}
+
+    async fn update(conn: &mut {conn_type}, id: &{id_type}, row: &{input_struct}) -> Result<{row_struct}, sqlx::Error> {{
+        sqlx::query(r#"UPDATE {table_name} SET {update_assignments} WHERE id = {update_id_placeholder}"#)
+        {update_binds}
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+        Self::select_by_id(conn, id).await
+    }}
+
+    async fn delete(conn: &mut {conn_type}, id: &{id_type}) -> Result<(), sqlx::Error> {{
+        sqlx::query(r#"DELETE FROM {table_name} WHERE id = {id_placeholder}"#)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }}
+}}"##,
+        column_list = p.column_list,
+        insert_placeholders = p.insert_placeholders,
+        insert_binds = p.insert_binds,
+        update_assignments = p.update_assignments,
+        update_binds = p.update_binds,
+        update_id_placeholder = p.update_id_placeholder,
+        id_placeholder = p.id_placeholder,
+    )
+}
+
+fn blocking_repository_impl(
+    backend: Backend,
+    trait_name: &str,
+    row_struct: &str,
+    input_struct: &str,
+    id_type: &str,
+    conn_type: &str,
+) -> String {
+    let impl_name = format!("Blocking{}Repository", row_struct);
+    let async_impl_name = format!("{}{}Repository", impl_name_prefix(backend), row_struct);
+
+    format!(
+        r#"#[cfg(feature = "blocking")]
+pub struct {impl_name};
+
+#[cfg(feature = "blocking")]
+impl {impl_name} {{
+    pub fn insert(conn: &mut {conn_type}, row: &{input_struct}) -> Result<{row_struct}, sqlx::Error> {{
+        futures::executor::block_on(<{async_impl_name} as {trait_name}>::insert(conn, row))
+    }}
+
+    pub fn select_by_id(conn: &mut {conn_type}, id: &{id_type}) -> Result<{row_struct}, sqlx::Error> {{
+        futures::executor::block_on(<{async_impl_name} as {trait_name}>::select_by_id(conn, id))
+    }}
+
+    pub fn select_all(conn: &mut {conn_type}) -> Result<Vec<{row_struct}>, sqlx::Error> {{
+        futures::executor::block_on(<{async_impl_name} as {trait_name}>::select_all(conn))
+    }}
+
+    pub fn update(conn: &mut {conn_type}, id: &{id_type}, row: &{input_struct}) -> Result<{row_struct}, sqlx::Error> {{
+        futures::executor::block_on(<{async_impl_name} as {trait_name}>::update(conn, id, row))
+    }}
+
+    pub fn delete(conn: &mut {conn_type}, id: &{id_type}) -> Result<(), sqlx::Error> {{
+        futures::executor::block_on(<{async_impl_name} as {trait_name}>::delete(conn, id))
+    }}
+}}"#
+    )
+}
@@ -0,0 +1,25 @@
+use codegen::Scope;
+use itertools::Itertools;
+
+use super::enum_type_name;
+
+/// Emits a Rust enum for a Postgres user-defined enum type, with one
+/// variant per label, tagged so `sqlx` can round-trip it as its
+/// underlying udt. Emitted as a raw block, like the repository and
+/// relationship code, since `#[sqlx(rename = "...")]` on individual
+/// variants isn't something the struct/fn builders model.
+pub fn add_enum(scope: &mut Scope, enum_name: &str, labels: &[String]) {
+    let variants = labels
+        .iter()
+        .map(|label| format!("    #[sqlx(rename = \"{label}\")]\n    {},", enum_type_name(label)))
+        .join("\n");
+
+    scope.raw(&format!(
+        r#"#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = "{enum_name}")]
+pub enum {rust_name} {{
+{variants}
+}}"#,
+        rust_name = enum_type_name(enum_name),
+    ));
+}
@@ -0,0 +1,82 @@
+mod crud;
+mod enums;
+mod relations;
+
+pub use crud::add_repository_for_table;
+pub use enums::add_enum;
+pub use relations::add_relationships_for_table;
+
+use codegen::Scope;
+use inflector::cases::classcase::to_class_case;
+
+use crate::config::Config;
+use crate::schema::TypeResolver;
+
+pub(crate) fn input_row_struct_name(table_name: &str) -> String {
+    format!("{}Input", to_class_case(table_name))
+}
+
+pub(crate) fn row_struct_name(table_name: &str) -> String {
+    format!("{}", to_class_case(table_name))
+}
+
+/// The Rust enum name generated for a Postgres enum udt named `enum_name`.
+pub(crate) fn enum_type_name(enum_name: &str) -> String {
+    to_class_case(enum_name)
+}
+
+fn apply_derives(new_struct: &mut codegen::Struct, config: &Config, extra: &[&str]) {
+    for derive in extra {
+        new_struct.derive(derive);
+    }
+    for derive in &config.derives {
+        new_struct.derive(derive);
+    }
+}
+
+/// `columns` is `(db_column_name, rust_field_name, udt_name, is_nullable, comment)`.
+pub fn add_structs_for_table(
+    scope: &mut Scope,
+    resolver: &TypeResolver,
+    config: &Config,
+    table_name: &str,
+    table_comment: Option<&str>,
+    columns: &[(String, String, String, bool, Option<String>)],
+) {
+    let new_struct = scope.new_struct(&row_struct_name(table_name));
+    new_struct.vis("pub");
+    apply_derives(new_struct, config, &["Debug", "Clone", "sqlx::FromRow"]);
+    if let Some(comment) = table_comment {
+        new_struct.doc(comment);
+    }
+    for column in columns {
+        let field = if !column.3 {
+            new_struct.new_field(&format!("pub {}", column.1), &resolver.resolve(&column.2))
+        } else {
+            new_struct.new_field(
+                &format!("pub {}", column.1),
+                format!("Option<{}>", resolver.resolve(&column.2)),
+            )
+        };
+        if let Some(comment) = &column.4 {
+            field.doc(comment);
+        }
+    }
+
+    let new_in_struct = scope.new_struct(&input_row_struct_name(table_name));
+    new_in_struct.vis("pub");
+    apply_derives(new_in_struct, config, &["Debug", "Clone"]);
+    for column in columns.iter().filter(|c| c.0 != "id") {
+        let field = if !column.3 {
+            new_in_struct.new_field(&format!("pub {}", column.1), &resolver.resolve(&column.2))
+        } else {
+            new_in_struct.new_field(
+                &format!("pub {}", column.1),
+                format!("Option<{}>", resolver.resolve(&column.2)),
+            )
+        };
+        if let Some(comment) = &column.4 {
+            field.doc(comment);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::{HashMap, HashSet};
+
+use codegen::Scope;
+
+use super::row_struct_name;
+use crate::config::Config;
+use crate::schema::{ForeignKey, TypeResolver};
+
+/// The naming "role" of a foreign key column, used to name its accessor
+/// method: `sender_id` becomes `sender`, a column with no `_id` suffix
+/// (e.g. `owner`) is used as-is.
+fn fk_role(column_name: &str) -> &str {
+    column_name.strip_suffix("_id").unwrap_or(column_name)
+}
+
+/// Returns `name`, or `name` suffixed with an incrementing number if it's
+/// already in `used`. Guards against two foreign keys producing the same
+/// method name and emitting an `impl` block that doesn't compile.
+fn dedupe(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{name}_{n}");
+        n += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+/// Emits relationship accessors on the row struct for `table_name`: one
+/// accessor per outgoing foreign key, named after the local FK column
+/// (`sender_id` -> `get_sender`), and one per foreign key in `incoming`
+/// pointing back at this table, named after the child table (`get_messages`)
+/// unless more than one of the child's FKs points here, in which case the
+/// child's FK column disambiguates it (`get_sender_messages`,
+/// `get_recipient_messages`). Turns the flat struct from
+/// `add_structs_for_table` into a navigable model, the same shape a DB
+/// explorer would expose.
+pub fn add_relationships_for_table(
+    scope: &mut Scope,
+    resolver: &TypeResolver,
+    config: &Config,
+    table_name: &str,
+    outgoing: &[ForeignKey],
+    incoming: &[ForeignKey],
+) {
+    if outgoing.is_empty() && incoming.is_empty() {
+        return;
+    }
+
+    let row_struct = row_struct_name(table_name);
+    let mut methods = String::new();
+    let mut used_names: HashSet<String> = HashSet::new();
+
+    for fk in outgoing {
+        let parent_struct = row_struct_name(&fk.referenced_table);
+        let placeholder = resolver.placeholder(1);
+        let field_name = config.field_name(&fk.column_name);
+        let method_name = dedupe(format!("get_{}", fk_role(&fk.column_name)), &mut used_names);
+        methods.push_str(&format!(
+            r#"
+    pub async fn {method_name}(&self, conn: &mut PgConnection) -> Result<{parent_struct}, sqlx::Error> {{
+        sqlx::query_as::<_, {parent_struct}>(r#"SELECT * FROM {referenced_table} WHERE {referenced_column} = {placeholder}"#)
+            .bind(&self.{field_name})
+            .fetch_one(&mut *conn)
+            .await
+    }}
+"#,
+            referenced_table = fk.referenced_table,
+            referenced_column = fk.referenced_column,
+        ));
+    }
+
+    let mut incoming_by_child: HashMap<&str, usize> = HashMap::new();
+    for fk in incoming {
+        *incoming_by_child.entry(fk.table_name.as_str()).or_default() += 1;
+    }
+
+    for fk in incoming {
+        let child_struct = row_struct_name(&fk.table_name);
+        let placeholder = resolver.placeholder(1);
+        let field_name = config.field_name(&fk.referenced_column);
+        let base_name = if incoming_by_child[fk.table_name.as_str()] > 1 {
+            format!("get_{}_{}s", fk_role(&fk.column_name), fk.table_name)
+        } else {
+            format!("get_{}s", fk.table_name)
+        };
+        let method_name = dedupe(base_name, &mut used_names);
+        methods.push_str(&format!(
+            r#"
+    pub async fn {method_name}(&self, conn: &mut PgConnection) -> Result<Vec<{child_struct}>, sqlx::Error> {{
+        sqlx::query_as::<_, {child_struct}>(r#"SELECT * FROM {child_table} WHERE {column_name} = {placeholder}"#)
+            .bind(&self.{field_name})
+            .fetch_all(&mut *conn)
+            .await
+    }}
+"#,
+            child_table = fk.table_name,
+            column_name = fk.column_name,
+        ));
+    }
+
+    scope.raw(&format!("impl {row_struct} {{{methods}}}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fk_role_strips_id_suffix() {
+        assert_eq!(fk_role("sender_id"), "sender");
+        assert_eq!(fk_role("recipient_id"), "recipient");
+    }
+
+    #[test]
+    fn fk_role_keeps_columns_without_id_suffix() {
+        assert_eq!(fk_role("owner"), "owner");
+    }
+
+    #[test]
+    fn dedupe_leaves_first_use_untouched() {
+        let mut used = HashSet::new();
+        assert_eq!(dedupe("get_sender".to_string(), &mut used), "get_sender");
+    }
+
+    #[test]
+    fn dedupe_numbers_repeated_names() {
+        let mut used = HashSet::new();
+        assert_eq!(dedupe("get_messages".to_string(), &mut used), "get_messages");
+        assert_eq!(dedupe("get_messages".to_string(), &mut used), "get_messages_2");
+        assert_eq!(dedupe("get_messages".to_string(), &mut used), "get_messages_3");
+    }
+}
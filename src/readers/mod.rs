@@ -0,0 +1,28 @@
+mod mysql;
+mod postgres;
+mod sqlite;
+
+pub use mysql::{mysql_type_to_rs_type, MySqlSchemaReader};
+pub use postgres::{pg_type_to_rs_type, PostgresSchemaReader};
+pub use sqlite::{sqlite_type_to_rs_type, SqliteSchemaReader};
+
+use crate::schema::{Backend, SchemaReader};
+
+/// Connects to `db_url` and returns the `SchemaReader` for whichever
+/// backend its scheme names.
+///
+/// Supported schemes: `postgres://`/`postgresql://`, `mysql://`, `sqlite:`.
+pub async fn reader_for_url(db_url: &str) -> Result<(Box<dyn SchemaReader>, Backend), anyhow::Error> {
+    if db_url.starts_with("postgres://") || db_url.starts_with("postgresql://") {
+        let reader = PostgresSchemaReader::connect(db_url).await?;
+        Ok((Box::new(reader), Backend::Postgres))
+    } else if db_url.starts_with("mysql://") {
+        let reader = MySqlSchemaReader::connect(db_url).await?;
+        Ok((Box::new(reader), Backend::MySql))
+    } else if db_url.starts_with("sqlite:") {
+        let reader = SqliteSchemaReader::connect(db_url).await?;
+        Ok((Box::new(reader), Backend::Sqlite))
+    } else {
+        anyhow::bail!("Unrecognised DATABASE_URL scheme in: {db_url}")
+    }
+}
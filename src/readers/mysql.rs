@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use sqlx::{mysql::MySqlPoolOptions, MySqlPool, Row};
+
+use crate::schema::{SchemaReader, TableDefinition};
+
+pub struct MySqlSchemaReader {
+    pool: MySqlPool,
+}
+
+impl MySqlSchemaReader {
+    pub async fn connect(db_url: &str) -> Result<Self, anyhow::Error> {
+        let pool = MySqlPoolOptions::new()
+            .max_connections(5)
+            .connect(db_url)
+            .await
+            .expect("Couldnt connect");
+
+        println!("connecting to db");
+        sqlx::query("SELECT 1;")
+            .execute(&pool)
+            .await
+            .expect("Could not connect");
+        println!("db connected");
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl SchemaReader for MySqlSchemaReader {
+    async fn read_tables(&self) -> Result<Vec<TableDefinition>, anyhow::Error> {
+        // `IS_NULLABLE = 'YES'` and `ORDINAL_POSITION` both come back from
+        // MySQL as (unsigned) integers rather than the real boolean/signed
+        // int Postgres reports, so they're decoded as `i64` here and
+        // converted by hand instead of relying on `query_as`'s strict
+        // column-type matching against `TableDefinition`.
+        let rows = sqlx::query(
+            "SELECT TABLE_NAME as table_name, COLUMN_NAME as column_name, CAST(IS_NULLABLE = 'YES' AS SIGNED) as is_nullable, DATA_TYPE as udt_name, CAST(ORDINAL_POSITION AS SIGNED) as ordinal_position, NULLIF(COLUMN_COMMENT, '') as comment FROM information_schema.columns WHERE table_schema = DATABASE() ORDER BY table_name, ordinal_position"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let tables = rows
+            .into_iter()
+            .map(|row| TableDefinition {
+                table_name: row.get::<String, _>("table_name"),
+                column_name: row.get::<String, _>("column_name"),
+                is_nullable: row.get::<i64, _>("is_nullable") != 0,
+                udt_name: row.get::<String, _>("udt_name"),
+                ordinal_position: row.get::<i64, _>("ordinal_position") as i32,
+                comment: row.get::<Option<String>, _>("comment"),
+            })
+            .collect();
+
+        Ok(tables)
+    }
+}
+
+/// Maps a MySQL `DATA_TYPE` (as reported by `information_schema.columns`)
+/// to the Rust type used for the generated struct field, or `None` if
+/// it's not recognised.
+///
+/// MySQL has no real boolean type: `BOOLEAN`/`BOOL` columns are just
+/// `TINYINT(1)`, and `DATA_TYPE` reports them as `tinyint` same as any
+/// other one-byte integer column, with the `(1)` display width only
+/// visible in `COLUMN_TYPE`, which this reader doesn't fetch. So there's
+/// no `"boolean" | "bool"` arm here — every MySQL `tinyint` column,
+/// boolean or not, surfaces as `i8`.
+pub fn mysql_type_to_rs_type(mysql_type: &str) -> Option<String> {
+    let rs_type = match mysql_type {
+        "bigint" => "i64",
+        "int" => "i32",
+        "smallint" => "i16",
+        "tinyint" => "i8",
+        "text" | "varchar" | "char" => "String",
+        "json" => "sqlx::Json",
+        "datetime" | "timestamp" => "chrono::DateTime<chrono::Utc>",
+        "date" => "chrono::NaiveDate",
+        "float" => "f32",
+        "double" => "f64",
+        "blob" | "varbinary" | "binary" => "Vec<u8>",
+        _ => return None,
+    };
+    Some(rs_type.to_string())
+}
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgPoolOptions, PgPool};
+
+use crate::schema::{EnumVariant, ForeignKey, SchemaReader, TableComment, TableDefinition};
+
+pub struct PostgresSchemaReader {
+    pool: PgPool,
+}
+
+impl PostgresSchemaReader {
+    pub async fn connect(db_url: &str) -> Result<Self, anyhow::Error> {
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(db_url)
+            .await
+            .expect("Couldnt connect");
+
+        println!("connecting to db");
+        sqlx::query("SELECT 1;")
+            .execute(&pool)
+            .await
+            .expect("Could not connect");
+        println!("db connected");
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl SchemaReader for PostgresSchemaReader {
+    async fn read_tables(&self) -> Result<Vec<TableDefinition>, anyhow::Error> {
+        let tables: Vec<TableDefinition> = sqlx::query_as(
+            r#"
+            SELECT
+                table_name,
+                column_name,
+                is_nullable = 'YES' as is_nullable,
+                udt_name,
+                ordinal_position,
+                col_description(format('%I.%I', table_schema, table_name)::regclass, ordinal_position) as comment
+            FROM information_schema.columns
+            WHERE table_schema='public'
+            ORDER BY table_name, ordinal_position
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tables)
+    }
+
+    async fn read_foreign_keys(&self) -> Result<Vec<ForeignKey>, anyhow::Error> {
+        let foreign_keys: Vec<ForeignKey> = sqlx::query_as(
+            r#"
+            SELECT
+                tc.table_name as table_name,
+                kcu.column_name as column_name,
+                ccu.table_name as referenced_table,
+                ccu.column_name as referenced_column
+            FROM information_schema.table_constraints tc
+            JOIN information_schema.key_column_usage kcu
+                ON tc.constraint_name = kcu.constraint_name
+                AND tc.table_schema = kcu.table_schema
+            JOIN information_schema.constraint_column_usage ccu
+                ON tc.constraint_name = ccu.constraint_name
+                AND tc.table_schema = ccu.table_schema
+            WHERE tc.constraint_type = 'FOREIGN KEY' AND tc.table_schema = 'public'
+            ORDER BY tc.table_name, kcu.column_name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(foreign_keys)
+    }
+
+    async fn read_enums(&self) -> Result<Vec<EnumVariant>, anyhow::Error> {
+        let variants: Vec<EnumVariant> = sqlx::query_as(
+            r#"
+            SELECT t.typname as enum_name, e.enumlabel as label
+            FROM pg_type t
+            JOIN pg_enum e ON t.oid = e.enumtypid
+            WHERE t.typcategory = 'E'
+            ORDER BY t.typname, e.enumsortorder
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(variants)
+    }
+
+    async fn read_table_comments(&self) -> Result<Vec<TableComment>, anyhow::Error> {
+        let comments: Vec<TableComment> = sqlx::query_as(
+            r#"
+            SELECT c.relname as table_name, obj_description(c.oid, 'pg_class') as comment
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE c.relkind = 'r' AND n.nspname = 'public' AND obj_description(c.oid, 'pg_class') IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(comments)
+    }
+}
+
+/// Maps a Postgres `udt_name` (as reported by `information_schema.columns`)
+/// to the Rust type used for the generated struct field, or `None` if
+/// it's not one of the scalar types this backend knows about outright
+/// (arrays and enum udt names are handled by `TypeResolver` instead).
+pub fn pg_type_to_rs_type(pg_type: &str) -> Option<String> {
+    let rs_type = match pg_type {
+        "int8" => "i64",
+        "int4" => "i32",
+        "int2" => "i16",
+        "text" => "String",
+        "varchar" => "String",
+        "jsonb" => "serde_json::Value",
+        "timestamptz" => "chrono::DateTime<chrono::Utc>",
+        "date" => "chrono::NaiveDate",
+        "float4" => "f32",
+        "float8" => "f64",
+        "uuid" => "uuid::Uuid",
+        "boolean" => "bool",
+        "bytea" => "Vec<u8>", // is this right?
+        _ => return None,
+    };
+    Some(rs_type.to_string())
+}
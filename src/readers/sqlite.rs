@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use sqlx::{sqlite::SqlitePoolOptions, Row, SqlitePool};
+
+use crate::schema::{SchemaReader, TableDefinition};
+
+pub struct SqliteSchemaReader {
+    pool: SqlitePool,
+}
+
+impl SqliteSchemaReader {
+    pub async fn connect(db_url: &str) -> Result<Self, anyhow::Error> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(db_url)
+            .await
+            .expect("Couldnt connect");
+
+        println!("connecting to db");
+        sqlx::query("SELECT 1;")
+            .execute(&pool)
+            .await
+            .expect("Could not connect");
+        println!("db connected");
+
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl SchemaReader for SqliteSchemaReader {
+    async fn read_tables(&self) -> Result<Vec<TableDefinition>, anyhow::Error> {
+        let table_names: Vec<String> =
+            sqlx::query("SELECT name FROM sqlite_master WHERE type='table'")
+                .fetch_all(&self.pool)
+                .await?
+                .iter()
+                .map(|row| row.get::<String, _>("name"))
+                .collect();
+
+        let mut tables = Vec::new();
+        for table_name in table_names {
+            // PRAGMA calls don't accept bind parameters, so the table name
+            // has to be interpolated; it comes from sqlite_master, not user input.
+            let pragma = format!("PRAGMA table_info({table_name})");
+            let columns = sqlx::query(&pragma).fetch_all(&self.pool).await?;
+            for column in columns {
+                tables.push(TableDefinition {
+                    table_name: table_name.clone(),
+                    column_name: column.get::<String, _>("name"),
+                    udt_name: column.get::<String, _>("type"),
+                    is_nullable: column.get::<i64, _>("notnull") == 0,
+                    ordinal_position: column.get::<i64, _>("cid") as i32,
+                    comment: None,
+                });
+            }
+        }
+
+        Ok(tables)
+    }
+}
+
+/// Maps a SQLite column type (as reported by `PRAGMA table_info`) to the
+/// Rust type used for the generated struct field, or `None` if it's not
+/// recognised.
+///
+/// `PRAGMA table_info` reports the *declared* type verbatim (`VARCHAR(255)`,
+/// `INTEGER PRIMARY KEY`, ...), not a normalised one, so this first strips
+/// any `(...)` length/precision suffix, checks a few exact declared names
+/// we give richer types to, then falls back to SQLite's own type-affinity
+/// rules (see <https://www.sqlite.org/datatype3.html#determination_of_column_affinity>)
+/// for everything else.
+pub fn sqlite_type_to_rs_type(sqlite_type: &str) -> Option<String> {
+    let base = sqlite_type
+        .split('(')
+        .next()
+        .unwrap_or(sqlite_type)
+        .trim()
+        .to_uppercase();
+
+    let rs_type = match base.as_str() {
+        "DATE" => "chrono::NaiveDate",
+        "DATETIME" | "TIMESTAMP" => "chrono::DateTime<chrono::Utc>",
+        "BOOLEAN" | "BOOL" => "bool",
+        _ if base.contains("INT") => "i64",
+        _ if base.contains("CHAR") || base.contains("CLOB") || base.contains("TEXT") => "String",
+        _ if base.contains("BLOB") || base.is_empty() => "Vec<u8>",
+        _ if base.contains("REAL") || base.contains("FLOA") || base.contains("DOUB") => "f64",
+        _ => return None,
+    };
+    Some(rs_type.to_string())
+}
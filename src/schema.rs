@@ -0,0 +1,269 @@
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use async_trait::async_trait;
+
+/// Which source database a `SchemaReader` was built for.
+///
+/// Besides picking the reader implementation, this also selects the
+/// udt-name-to-Rust-type mapping, since each backend names its column
+/// types differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Backend {
+    /// Maps the column type name reported by this backend's schema reader
+    /// to the Rust type used for the generated struct field, or `None` if
+    /// this backend doesn't recognise it. Callers needing Postgres array
+    /// and enum support, or a fallback for unrecognised types, should go
+    /// through `TypeResolver::resolve` instead.
+    fn type_to_rs_type_checked(&self, udt_name: &str) -> Option<String> {
+        match self {
+            Backend::Postgres => crate::readers::pg_type_to_rs_type(udt_name),
+            Backend::MySql => crate::readers::mysql_type_to_rs_type(udt_name),
+            Backend::Sqlite => crate::readers::sqlite_type_to_rs_type(udt_name),
+        }
+    }
+
+    /// The bind-parameter placeholder for the `index`th (1-based) argument
+    /// of a query on this backend.
+    pub fn placeholder(&self, index: usize) -> String {
+        match self {
+            Backend::Postgres => format!("${}", index),
+            Backend::MySql | Backend::Sqlite => "?".to_string(),
+        }
+    }
+
+    /// The `sqlx` connection type generated repository code is written
+    /// against for this backend.
+    pub fn connection_type(&self) -> &'static str {
+        match self {
+            Backend::Postgres => "PgConnection",
+            Backend::MySql => "MySqlConnection",
+            Backend::Sqlite => "SqliteConnection",
+        }
+    }
+}
+
+/// What to do with a column type none of the backend's type maps, arrays,
+/// or known enums recognise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownTypePolicy {
+    /// Map it to `serde_json::Value` and move on without comment.
+    Placeholder,
+    /// Map it to `serde_json::Value`, and print every such type found
+    /// once generation finishes, instead of aborting on the first one.
+    CollectAndReport,
+}
+
+/// Resolves column type names to Rust types for one generation run.
+///
+/// Wraps a `Backend`'s plain type map with Postgres array unwrapping
+/// (`_text` -> `Vec<String>`), lookups against the enum types discovered
+/// in this database, and a non-panicking fallback for anything still
+/// unrecognised.
+pub struct TypeResolver {
+    backend: Backend,
+    enums: HashSet<String>,
+    overrides: HashMap<String, String>,
+    policy: UnknownTypePolicy,
+    unknown_types: RefCell<BTreeSet<String>>,
+}
+
+impl TypeResolver {
+    pub fn new(
+        backend: Backend,
+        enums: HashSet<String>,
+        overrides: HashMap<String, String>,
+        policy: UnknownTypePolicy,
+    ) -> Self {
+        Self {
+            backend,
+            enums,
+            overrides,
+            policy,
+            unknown_types: RefCell::new(BTreeSet::new()),
+        }
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    pub fn placeholder(&self, index: usize) -> String {
+        self.backend.placeholder(index)
+    }
+
+    pub fn connection_type(&self) -> &'static str {
+        self.backend.connection_type()
+    }
+
+    pub fn resolve(&self, udt_name: &str) -> String {
+        if let Some(rs_type) = self.overrides.get(udt_name) {
+            return rs_type.clone();
+        }
+
+        if self.backend == Backend::Postgres {
+            if let Some(element) = udt_name.strip_prefix('_') {
+                return format!("Vec<{}>", self.resolve(element));
+            }
+            if self.enums.contains(udt_name) {
+                return crate::emit::enum_type_name(udt_name);
+            }
+        }
+
+        if let Some(rs_type) = self.backend.type_to_rs_type_checked(udt_name) {
+            return rs_type;
+        }
+
+        self.unknown_types.borrow_mut().insert(udt_name.to_string());
+        "serde_json::Value".to_string()
+    }
+
+    /// Prints a summary of every unrecognised column type this resolver
+    /// fell back on, if the policy asked for one.
+    pub fn report_unknown_types(&self) {
+        if self.policy != UnknownTypePolicy::CollectAndReport {
+            return;
+        }
+        let unknown = self.unknown_types.borrow();
+        if !unknown.is_empty() {
+            println!(
+                "Unknown column types mapped to serde_json::Value: {}",
+                unknown.iter().cloned().collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+}
+
+/// One column of one table, as reported by the source database's schema
+/// introspection tables/pragmas.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TableDefinition {
+    pub table_name: String,
+    pub column_name: String,
+    pub udt_name: String,
+    pub is_nullable: bool,
+    pub ordinal_position: i32,
+    /// The column's description, if the source database has doc comments
+    /// (Postgres `COMMENT ON COLUMN`) and the reader knows how to fetch them.
+    pub comment: Option<String>,
+}
+
+/// A table's description, if it has one (Postgres `COMMENT ON TABLE`).
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TableComment {
+    pub table_name: String,
+    pub comment: String,
+}
+
+/// A foreign key constraint: `table_name.column_name` references
+/// `referenced_table.referenced_column`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ForeignKey {
+    pub table_name: String,
+    pub column_name: String,
+    pub referenced_table: String,
+    pub referenced_column: String,
+}
+
+/// One variant of a Postgres user-defined enum type, as reported by
+/// `pg_type`/`pg_enum`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct EnumVariant {
+    pub enum_name: String,
+    pub label: String,
+}
+
+/// Reads table/column metadata out of a source database.
+///
+/// Each supported backend (Postgres, MySQL, SQLite) implements this with
+/// its own introspection query and its own column-type names, but `do_it`
+/// only ever talks to this trait, so adding a new backend doesn't touch
+/// the codegen side at all.
+#[async_trait]
+pub trait SchemaReader {
+    async fn read_tables(&self) -> Result<Vec<TableDefinition>, anyhow::Error>;
+
+    /// Reads the foreign key constraints declared on this database.
+    ///
+    /// Defaults to reporting none, since not every backend's information
+    /// schema exposes these the same way; Postgres overrides this.
+    async fn read_foreign_keys(&self) -> Result<Vec<ForeignKey>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+
+    /// Reads the variants of every user-defined enum type in this
+    /// database. Defaults to reporting none; only Postgres has these.
+    async fn read_enums(&self) -> Result<Vec<EnumVariant>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+
+    /// Reads table-level descriptions. Defaults to reporting none; only
+    /// Postgres has these.
+    async fn read_table_comments(&self) -> Result<Vec<TableComment>, anyhow::Error> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver(backend: Backend, enums: &[&str]) -> TypeResolver {
+        TypeResolver::new(
+            backend,
+            enums.iter().map(|e| e.to_string()).collect(),
+            HashMap::new(),
+            UnknownTypePolicy::Placeholder,
+        )
+    }
+
+    #[test]
+    fn resolve_unwraps_postgres_arrays() {
+        let resolver = resolver(Backend::Postgres, &[]);
+        assert_eq!(resolver.resolve("_text"), "Vec<String>");
+        assert_eq!(resolver.resolve("_int4"), "Vec<i32>");
+    }
+
+    #[test]
+    fn resolve_unwraps_nested_postgres_arrays() {
+        let resolver = resolver(Backend::Postgres, &[]);
+        assert_eq!(resolver.resolve("__text"), "Vec<Vec<String>>");
+    }
+
+    #[test]
+    fn resolve_does_not_unwrap_underscore_prefix_on_other_backends() {
+        // MySQL/SQLite udt names are never array-prefixed like Postgres's,
+        // so an unrecognised leading underscore should just fall through
+        // to the unknown-type placeholder rather than being unwrapped.
+        let resolver = resolver(Backend::MySql, &[]);
+        assert_eq!(resolver.resolve("_text"), "serde_json::Value");
+    }
+
+    #[test]
+    fn resolve_prefers_overrides_and_enums() {
+        let mut overrides = HashMap::new();
+        overrides.insert("jsonb".to_string(), "MyJson".to_string());
+        let resolver = TypeResolver::new(
+            Backend::Postgres,
+            ["mood".to_string()].into_iter().collect(),
+            overrides,
+            UnknownTypePolicy::Placeholder,
+        );
+        assert_eq!(resolver.resolve("jsonb"), "MyJson");
+        assert_eq!(resolver.resolve("mood"), "Mood");
+    }
+
+    #[test]
+    fn placeholder_is_backend_specific() {
+        assert_eq!(Backend::Postgres.placeholder(1), "$1");
+        assert_eq!(Backend::Postgres.placeholder(2), "$2");
+        assert_eq!(Backend::MySql.placeholder(1), "?");
+        assert_eq!(Backend::Sqlite.placeholder(1), "?");
+    }
+}